@@ -1,51 +1,99 @@
-#![allow(unused)]
-
-use std::io::{Stderr, Stdout, StdoutLock, Write};
-use std::marker::PhantomData;
-use std::process::Stdio;
+use std::fmt;
+use std::io::Write;
 use std::sync::mpsc::{Receiver, Sender};
 use std::sync::{mpsc, Arc, Mutex};
 use std::time::Duration;
 use std::{io, thread};
 
 fn main() {
-    let (sender, receiver) = mpsc::channel::<Message>();
+    let (sender, receiver) = crossbeam_channel::unbounded::<Message>();
     let (disconnect_sender, disconnect_receiver) = mpsc::channel::<Disconnect>();
 
-    let mut reporter = CargoMsrvReporter::setup(sender, disconnect_receiver);
+    let mut reporter = CargoMsrvReporter::setup(sender.clone(), disconnect_receiver);
 
     let indicatif_handler = IndicatifHandler::default();
-    let json_handler = JsonHandler::default();
+    let serializing_handler = SerializingHandler::new(Format::Json, io::stderr())
+        .with_terminal_sink(indicatif_handler.terminal_sink());
+    let tracing_handler = TracingHandler::default();
     let multi_handler = MultiHandler::new()
-        .push(Box::new(json_handler))
-        .push(Box::new(indicatif_handler));
-
-    let _writer = CargoMsrvWriter::setup(receiver, disconnect_sender, multi_handler);
-
-    reporter.report_event(Message::CurrentStatus("chris!".into()));
-    reporter.report_event(Message::CurrentStatus("jean!".into()));
-    reporter.report_event(Message::Progression(Progression {
-        current: 1,
-        max: 10,
-    }));
-    reporter.report_event(Message::CurrentStatus("chris!".into()));
-
-    reporter.report_event(Message::Event(Event::Installing));
-
-    reporter.report_event(Message::Progression(Progression {
-        current: 5,
-        max: 10,
-    }));
-    reporter.report_event(Message::Event(Event::Installing));
+        .push(Box::new(serializing_handler))
+        .push(Box::new(indicatif_handler))
+        .push(Box::new(tracing_handler));
+
+    let writer = CargoMsrvWriter::setup(receiver, sender, disconnect_sender, multi_handler, |handler| {
+        handler.handle(Message::CurrentStatus("cargo-msrv starting up".into()))
+    });
+
+    // Wire the writer's cancellation sender to a SIGINT handler so Ctrl+C triggers a
+    // clean shutdown instead of leaving a half-drawn progress bar and mangled
+    // terminal state behind.
+    let cancel_sender = writer.cancel_sender();
+    ctrlc::set_handler(move || {
+        let _ = cancel_sender.send(Cancel);
+    })
+    .expect("failed to install Ctrl+C handler");
+
+    // A second producer, independent of `reporter`, driving the same handler pipeline
+    // through the writer's back-door sender - e.g. a heartbeat ticker that pings status
+    // while the main search below reports progression.
+    let heartbeat_sender = writer.sender();
+    thread::spawn(move || {
+        for _ in 0..2 {
+            thread::sleep(Duration::from_secs(5));
+            let _ = heartbeat_sender.send(Message::CurrentStatus("still working...".into()));
+        }
+    });
 
-    reporter.report_event(Message::Progression(Progression {
-        current: 10,
-        max: 10,
-    }));
+    // `writer` itself retains a back-door `Sender<Message>` (see `CargoMsrvWriter::sender`),
+    // and `Reporter::disconnect` only returns once every `Sender<Message>` clone is
+    // dropped - including this one. We've already pulled out the clones we need
+    // (`cancel_sender`, `heartbeat_sender`), so drop `writer` now rather than letting it
+    // outlive `disconnect` and hang forever.
+    drop(writer);
 
-    reporter.report_event(Message::Event(Event::Installing));
+    {
+        let mut report = |event: Message| {
+            if let Err(err) = reporter.report_event(event) {
+                eprintln!("failed to report event: {}", err);
+            }
+        };
+
+        report(Message::CurrentStatus("chris!".into()));
+        report(Message::CurrentStatus("jean!".into()));
+        report(Message::Progression(Progression {
+            current: 1,
+            max: 10,
+        }));
+        report(Message::CurrentStatus("chris!".into()));
+
+        report(Message::Event(Event::Installing));
+
+        report(Message::Progression(Progression {
+            current: 5,
+            max: 10,
+        }));
+        report(Message::Event(Event::Installing));
+
+        report(Message::Progression(Progression {
+            current: 10,
+            max: 10,
+        }));
+
+        report(Message::Event(Event::Installing));
+    }
 
-    let _ = reporter.disconnect();
+    let disconnect = reporter.disconnect();
+    if let Some(err) = disconnect.last_error {
+        eprintln!(
+            "writer reported an error after processing {} event(s): {}",
+            disconnect.events_processed, err
+        );
+    } else {
+        eprintln!(
+            "writer shut down cleanly after processing {} event(s)",
+            disconnect.events_processed
+        );
+    }
 }
 
 trait Reporter {
@@ -54,7 +102,7 @@ trait Reporter {
     type Err;
 
     fn setup(
-        sender: mpsc::Sender<Self::Event>,
+        sender: crossbeam_channel::Sender<Self::Event>,
         disconnect_receiver: mpsc::Receiver<Self::Disconnect>,
     ) -> Self;
 
@@ -63,7 +111,45 @@ trait Reporter {
     fn disconnect(self) -> Disconnect;
 }
 
+/// The error type threaded through the reporting pipeline: from
+/// [`Reporter::report_event`] down to [`EventHandler::handle`] and back up in the
+/// [`Disconnect`] a caller receives from [`Reporter::disconnect`].
+#[derive(Debug)]
+enum HandlerError {
+    /// The channel to the writer thread is gone; the event was never queued.
+    SendFailed,
+    /// The handler (or its sink) has already been closed and can't accept more events.
+    Closed,
+    /// Encoding the event for the wire failed.
+    SerializeFailed(EncodeError),
+    /// Writing or flushing the encoded event failed.
+    Io(io::Error),
+    /// One or more handlers in a [`MultiHandler`] failed; the others may have succeeded.
+    Multiple(Vec<HandlerError>),
+}
+
+impl fmt::Display for HandlerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::SendFailed => write!(f, "failed to send event: channel is closed"),
+            Self::Closed => write!(f, "handler is closed"),
+            Self::SerializeFailed(err) => write!(f, "failed to serialize event: {}", err),
+            Self::Io(err) => write!(f, "failed to write event: {}", err),
+            Self::Multiple(errors) => {
+                write!(f, "{} handler(s) failed:", errors.len())?;
+                for error in errors {
+                    write!(f, " [{}]", error)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::error::Error for HandlerError {}
+
 #[derive(serde::Serialize, Clone)]
+#[cfg_attr(test, derive(serde::Deserialize, Debug, PartialEq))]
 enum Message {
     Event(Event),
     CurrentStatus(String),
@@ -71,30 +157,77 @@ enum Message {
 }
 
 #[derive(serde::Serialize, Clone)]
+#[cfg_attr(test, derive(serde::Deserialize, Debug, PartialEq))]
 enum Event {
     Installing,
+    /// Not yet emitted by `main`'s demo search, but part of the wire format other
+    /// `EventHandler`s (and external consumers decoding [`SerializingHandler`]'s
+    /// output) already match on.
+    #[allow(dead_code)]
     Updating(String),
 }
 
 #[derive(serde::Serialize, Clone)]
+#[cfg_attr(test, derive(serde::Deserialize, Debug, PartialEq))]
 struct Progression {
     max: u64,
     current: u64,
 }
 
-struct Disconnect;
+/// Returned from [`Reporter::disconnect`] once the writer thread has shut down.
+///
+/// When the writer shuts down because every event sender was dropped, every
+/// [`Message`] that was queued before then has already been handed to the handler:
+/// a `crossbeam_channel::Receiver` keeps yielding buffered messages until the channel
+/// is both empty *and* disconnected, so the writer only reaches its "sender closed"
+/// branch after the queue has fully drained. When it shuts down on cancellation
+/// instead (a [`Cancel`] arrives, or every cancel sender has been dropped), any
+/// still-queued messages are dropped unprocessed.
+struct Disconnect {
+    /// The most recent [`HandlerError`] the writer observed, from the `on_init`
+    /// callback or while draining the queue, so a caller can tell whether all events
+    /// were actually flushed rather than silently lost.
+    last_error: Option<HandlerError>,
+    /// How many events the writer handed to the handler before shutting down.
+    events_processed: usize,
+}
+
+/// Error returned by [`CargoMsrvReporter::disconnect_with_timeout`].
+///
+/// `disconnect_with_timeout` isn't wired into `main`'s demo shutdown path (which uses
+/// the plain, blocking [`Reporter::disconnect`]), but it and this error type are
+/// exercised directly by tests.
+#[allow(dead_code)]
+#[derive(Debug)]
+enum DisconnectError {
+    /// The writer thread didn't acknowledge shutdown within the deadline.
+    Timeout,
+}
+
+impl fmt::Display for DisconnectError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Timeout => write!(f, "writer thread did not acknowledge shutdown in time"),
+        }
+    }
+}
+
+impl std::error::Error for DisconnectError {}
 
 struct CargoMsrvReporter {
-    sender: mpsc::Sender<Message>,
+    sender: crossbeam_channel::Sender<Message>,
     disconnect_receiver: mpsc::Receiver<Disconnect>,
 }
 
 impl Reporter for CargoMsrvReporter {
     type Event = Message;
     type Disconnect = Disconnect;
-    type Err = ();
+    type Err = HandlerError;
 
-    fn setup(sender: Sender<Self::Event>, disconnect_receiver: Receiver<Self::Disconnect>) -> Self {
+    fn setup(
+        sender: crossbeam_channel::Sender<Self::Event>,
+        disconnect_receiver: Receiver<Self::Disconnect>,
+    ) -> Self {
         Self {
             sender,
             disconnect_receiver,
@@ -102,7 +235,7 @@ impl Reporter for CargoMsrvReporter {
     }
 
     fn report_event(&mut self, event: Self::Event) -> Result<(), Self::Err> {
-        self.sender.send(event).map_err(|_| ())
+        self.sender.send(event).map_err(|_| HandlerError::SendFailed)
     }
 
     fn disconnect(self) -> Disconnect {
@@ -112,63 +245,182 @@ impl Reporter for CargoMsrvReporter {
     }
 }
 
+impl CargoMsrvReporter {
+    /// Like [`Reporter::disconnect`], but returns a [`DisconnectError::Timeout`]
+    /// instead of panicking if the writer thread doesn't acknowledge shutdown within
+    /// `timeout` (e.g. because it died without sending a [`Disconnect`]).
+    #[allow(dead_code)]
+    fn disconnect_with_timeout(self, timeout: Duration) -> Result<Disconnect, DisconnectError> {
+        drop(self.sender);
+
+        self.disconnect_receiver
+            .recv_timeout(timeout)
+            .map_err(|_| DisconnectError::Timeout)
+    }
+}
+
+/// Sent on the writer's cancellation channel to request an early, clean shutdown -
+/// e.g. from a `ctrlc`-style SIGINT handler.
+struct Cancel;
+
+/// Note: this struct holds its own `Sender<Message>` clone (for [`CargoMsrvWriter::sender`]),
+/// which counts toward the channel's live-sender count like any other. `Reporter::disconnect`
+/// only returns (and `disconnect_with_timeout` only succeeds) once every clone - the
+/// `Reporter`'s, every back-door clone handed out, *and* this one - has been dropped, so
+/// `CargoMsrvWriter` must be dropped before disconnecting or the writer thread will never
+/// observe the channel closing.
 struct CargoMsrvWriter {
-    handle: thread::JoinHandle<()>,
+    // Never joined: the writer is detached and communicated with exclusively through
+    // the event/cancel/disconnect channels, matching how `main` never awaits it either.
+    _handle: thread::JoinHandle<()>,
+    cancel_sender: crossbeam_channel::Sender<Cancel>,
+    sender: crossbeam_channel::Sender<Message>,
+}
+
+impl CargoMsrvWriter {
+    /// A cloneable handle that can be wired to an external signal handler (e.g.
+    /// `ctrlc::set_handler`) to request cancellation of this writer.
+    fn cancel_sender(&self) -> crossbeam_channel::Sender<Cancel> {
+        self.cancel_sender.clone()
+    }
+
+    /// A cloneable back-door into this writer's event stream, separate from the
+    /// [`Reporter`]'s sender. Lets auxiliary producers - a heartbeat ticker, a
+    /// command listener - inject events into the same handler pipeline without
+    /// owning the `Reporter`.
+    ///
+    /// Every clone handed out here - and `self`'s own copy - must be dropped before
+    /// `Reporter::disconnect` can return; see the struct-level note.
+    fn sender(&self) -> crossbeam_channel::Sender<Message> {
+        self.sender.clone()
+    }
 }
 
 trait EventWriter {
     type Event;
     type Disconnect;
 
-    fn setup<H>(
-        receiver: mpsc::Receiver<Self::Event>,
+    fn setup<H, F>(
+        receiver: crossbeam_channel::Receiver<Self::Event>,
+        backdoor_sender: crossbeam_channel::Sender<Self::Event>,
         disconnect_sender: mpsc::Sender<Self::Disconnect>,
         handler: H,
+        on_init: F,
     ) -> Self
     where
-        H: EventHandler<Event = Self::Event>;
+        H: EventHandler<Event = Self::Event>,
+        F: FnOnce(&mut H) -> Result<(), HandlerError> + Send + 'static;
 }
 
 impl EventWriter for CargoMsrvWriter {
     type Event = Message;
     type Disconnect = Disconnect;
 
-    fn setup<H>(
-        receiver: Receiver<Self::Event>,
+    fn setup<H, F>(
+        receiver: crossbeam_channel::Receiver<Self::Event>,
+        backdoor_sender: crossbeam_channel::Sender<Self::Event>,
         disconnect_sender: Sender<Self::Disconnect>,
         handler: H,
+        on_init: F,
     ) -> Self
     where
         H: EventHandler<Event = Self::Event>,
+        F: FnOnce(&mut H) -> Result<(), HandlerError> + Send + 'static,
     {
+        let (cancel_sender, cancel_receiver) = crossbeam_channel::unbounded::<Cancel>();
+        let sender = backdoor_sender;
+
         let handle = thread::spawn(move || {
             let disconnect_sender = disconnect_sender;
+            let mut handler = handler;
+            let mut last_error = None;
+            let mut events_processed = 0usize;
+
+            if let Err(err) = on_init(&mut handler) {
+                last_error = Some(err);
+            }
 
             loop {
-                match receiver.recv() {
-                    Ok(message) => handler.handle(message),
-                    Err(_e) => {
+                crossbeam_channel::select! {
+                    recv(receiver) -> message => match message {
+                        Ok(message) => {
+                            if let Err(err) = handler.handle(message) {
+                                last_error = Some(err);
+                            }
+                            events_processed += 1;
+                        }
+                        Err(_e) => {
+                            // `recv` only errors once the channel is both empty and
+                            // disconnected, so every queued message has already been
+                            // handled by this point - it's safe to finish up.
+                            handler.finish();
+                            eprintln!("\n\nSender closed!");
+                            disconnect_sender
+                                .send(Disconnect {
+                                    last_error,
+                                    events_processed,
+                                })
+                                .unwrap();
+                            break;
+                        }
+                    },
+                    recv(cancel_receiver) -> _ => {
+                        // Cancelled: stop draining immediately, finish the handler
+                        // (clearing the indicatif bar and restoring the cursor) and
+                        // drop any still-queued events rather than processing them.
                         handler.finish();
-                        eprintln!("\n\nSender closed!");
-                        disconnect_sender.send(Disconnect).unwrap();
+                        disconnect_sender
+                            .send(Disconnect {
+                                last_error,
+                                events_processed,
+                            })
+                            .unwrap();
                         break;
                     }
                 }
             }
         });
 
-        Self { handle }
+        Self {
+            _handle: handle,
+            cancel_sender,
+            sender,
+        }
     }
 }
 
 trait EventHandler: Send + 'static {
     type Event;
 
-    fn handle(&self, event: Self::Event);
+    fn handle(&self, event: Self::Event) -> Result<(), HandlerError>;
 
     fn finish(&self);
 }
 
+/// A cloneable reference to an [`IndicatifHandler`]'s progress bar, held by other
+/// handlers that need to print lines to the same terminal without corrupting it.
+///
+/// Every write must go through [`TerminalSink::suspend`]: the bar is never drawn
+/// while `f` runs, so nothing else can interleave output with a half-drawn bar.
+#[derive(Clone)]
+struct TerminalSink {
+    bar: indicatif::ProgressBar,
+}
+
+impl TerminalSink {
+    fn new(bar: indicatif::ProgressBar) -> Self {
+        Self { bar }
+    }
+
+    /// Runs `f` with the progress bar cleared, redrawing it once `f` returns.
+    fn suspend<F, T>(&self, f: F) -> T
+    where
+        F: FnOnce() -> T,
+    {
+        self.bar.suspend(f)
+    }
+}
+
 struct IndicatifHandler {
     bar: indicatif::ProgressBar,
 }
@@ -176,18 +428,26 @@ struct IndicatifHandler {
 impl Default for IndicatifHandler {
     fn default() -> Self {
         let bar = indicatif::ProgressBar::new(10);
-        bar.enable_steady_tick(250);
+        bar.enable_steady_tick(Duration::from_millis(250));
 
         Self { bar }
     }
 }
 
+impl IndicatifHandler {
+    /// A [`TerminalSink`] other handlers can borrow to print lines without garbling
+    /// this handler's progress bar.
+    fn terminal_sink(&self) -> TerminalSink {
+        TerminalSink::new(self.bar.clone())
+    }
+}
+
 impl EventHandler for IndicatifHandler {
     type Event = Message;
 
-    fn handle(&self, event: Self::Event) {
+    fn handle(&self, event: Self::Event) -> Result<(), HandlerError> {
         match event {
-            Message::Event(e) => {
+            Message::Event(_e) => {
                 thread::sleep(Duration::from_secs(2));
                 self.bar
                     .set_message(format!("Event ({})", self.bar.position()))
@@ -198,6 +458,8 @@ impl EventHandler for IndicatifHandler {
                 self.bar.set_position(p.current);
             }
         }
+
+        Ok(())
     }
 
     fn finish(&self) {
@@ -205,27 +467,201 @@ impl EventHandler for IndicatifHandler {
     }
 }
 
-struct JsonHandler {
-    stdout: Arc<Mutex<Stderr>>,
+/// An [`EventHandler`] that records each [`Message`] as a `tracing` event inside a
+/// single `msrv_search` span, updating the span's `current`/`max` fields as
+/// [`Progression`] events arrive.
+///
+/// This only names the span and emits events; wiring up a `tracing::Subscriber` (and
+/// any layers) is left entirely to the caller.
+struct TracingHandler {
+    span: tracing::Span,
 }
 
-impl Default for JsonHandler {
+impl Default for TracingHandler {
     fn default() -> Self {
         Self {
-            stdout: Arc::new(Mutex::new(io::stderr())),
+            span: tracing::info_span!("msrv_search", current = 0u64, max = 0u64),
         }
     }
 }
 
-impl EventHandler for JsonHandler {
+impl EventHandler for TracingHandler {
     type Event = Message;
 
-    fn handle(&self, event: Self::Event) {
-        let message = serde_json::to_string(&event).unwrap_or_default();
+    fn handle(&self, event: Self::Event) -> Result<(), HandlerError> {
+        let _entered = self.span.enter();
 
-        let mut out = self.stdout.lock().unwrap();
-        write!(out, "{}\n", message);
-        out.flush();
+        match event {
+            Message::Event(Event::Installing) => {
+                tracing::event!(tracing::Level::INFO, "installing toolchain");
+            }
+            Message::Event(Event::Updating(pkg)) => {
+                tracing::event!(tracing::Level::INFO, package = %pkg, "updating package");
+            }
+            Message::CurrentStatus(status) => {
+                tracing::event!(tracing::Level::INFO, %status, "current status");
+            }
+            Message::Progression(p) => {
+                self.span.record("current", p.current);
+                self.span.record("max", p.max);
+                tracing::event!(tracing::Level::INFO, current = p.current, max = p.max, "progression");
+            }
+        }
+
+        Ok(())
+    }
+
+    fn finish(&self) {}
+}
+
+/// An output encoding that a [`SerializingHandler`] can serialize a [`Message`] to.
+///
+/// This decouples the wire format from the handler plumbing, so new formats can be
+/// added without touching [`EventHandler`], [`MultiHandler`] or the writer thread.
+trait OutputFormat: Send + Sync + 'static {
+    fn encode(&self, msg: &Message) -> Result<Vec<u8>, EncodeError>;
+}
+
+/// The serialization formats built into `cargo-msrv`, selectable at construction time
+/// via [`SerializingHandler::new`].
+///
+/// Only `Json` is wired up in `main`'s demo pipeline; the rest are here for external
+/// consumers to select and are exercised by the `OutputFormat` roundtrip tests.
+enum Format {
+    /// Newline-delimited JSON, the historical default (stderr, one object per line).
+    Json,
+    /// [MessagePack](https://msgpack.org/), via `rmp-serde`.
+    #[allow(dead_code)]
+    MessagePack,
+    /// [`postcard`], a compact `no_std`-friendly binary format.
+    #[allow(dead_code)]
+    Postcard,
+    /// [`bincode`].
+    #[allow(dead_code)]
+    Bincode,
+}
+
+impl Format {
+    fn encoder(&self) -> Box<dyn OutputFormat> {
+        match self {
+            Format::Json => Box::new(JsonFormat),
+            Format::MessagePack => Box::new(MessagePackFormat),
+            Format::Postcard => Box::new(PostcardFormat),
+            Format::Bincode => Box::new(BincodeFormat),
+        }
+    }
+}
+
+#[derive(Debug)]
+enum EncodeError {
+    Json(serde_json::Error),
+    MessagePack(rmp_serde::encode::Error),
+    Postcard(postcard::Error),
+    Bincode(bincode::Error),
+}
+
+impl fmt::Display for EncodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Json(err) => write!(f, "json: {}", err),
+            Self::MessagePack(err) => write!(f, "messagepack: {}", err),
+            Self::Postcard(err) => write!(f, "postcard: {}", err),
+            Self::Bincode(err) => write!(f, "bincode: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for EncodeError {}
+
+struct JsonFormat;
+
+impl OutputFormat for JsonFormat {
+    fn encode(&self, msg: &Message) -> Result<Vec<u8>, EncodeError> {
+        let mut bytes = serde_json::to_vec(msg).map_err(EncodeError::Json)?;
+        bytes.push(b'\n');
+        Ok(bytes)
+    }
+}
+
+struct MessagePackFormat;
+
+impl OutputFormat for MessagePackFormat {
+    fn encode(&self, msg: &Message) -> Result<Vec<u8>, EncodeError> {
+        rmp_serde::to_vec(msg).map_err(EncodeError::MessagePack)
+    }
+}
+
+struct PostcardFormat;
+
+impl OutputFormat for PostcardFormat {
+    fn encode(&self, msg: &Message) -> Result<Vec<u8>, EncodeError> {
+        postcard::to_allocvec(msg).map_err(EncodeError::Postcard)
+    }
+}
+
+struct BincodeFormat;
+
+impl OutputFormat for BincodeFormat {
+    fn encode(&self, msg: &Message) -> Result<Vec<u8>, EncodeError> {
+        bincode::serialize(msg).map_err(EncodeError::Bincode)
+    }
+}
+
+/// An [`EventHandler`] which encodes every [`Message`] with a pluggable [`OutputFormat`]
+/// and writes the resulting bytes to `W`.
+///
+/// If a [`TerminalSink`] is attached via [`SerializingHandler::with_terminal_sink`],
+/// each write is routed through it so it can't interleave with a progress bar drawn to
+/// the same terminal; otherwise writes go straight to `W`.
+struct SerializingHandler<W> {
+    format: Box<dyn OutputFormat>,
+    writer: Arc<Mutex<W>>,
+    terminal: Option<TerminalSink>,
+}
+
+impl<W> SerializingHandler<W>
+where
+    W: Write + Send + 'static,
+{
+    fn new(format: Format, writer: W) -> Self {
+        Self {
+            format: format.encoder(),
+            writer: Arc::new(Mutex::new(writer)),
+            terminal: None,
+        }
+    }
+
+    /// Route this handler's writes through `sink`, so a line prints atomically while a
+    /// progress bar sharing the same terminal is cleared and then redrawn, instead of
+    /// racing it.
+    fn with_terminal_sink(mut self, sink: TerminalSink) -> Self {
+        self.terminal = Some(sink);
+        self
+    }
+}
+
+impl<W> EventHandler for SerializingHandler<W>
+where
+    W: Write + Send + 'static,
+{
+    type Event = Message;
+
+    fn handle(&self, event: Self::Event) -> Result<(), HandlerError> {
+        let bytes = self
+            .format
+            .encode(&event)
+            .map_err(HandlerError::SerializeFailed)?;
+
+        let write = || -> Result<(), HandlerError> {
+            let mut out = self.writer.lock().map_err(|_| HandlerError::Closed)?;
+            out.write_all(&bytes).map_err(HandlerError::Io)?;
+            out.flush().map_err(HandlerError::Io)
+        };
+
+        match &self.terminal {
+            Some(sink) => sink.suspend(write),
+            None => write(),
+        }
     }
 
     fn finish(&self) {}
@@ -251,9 +687,17 @@ impl MultiHandler {
 impl EventHandler for MultiHandler {
     type Event = Message;
 
-    fn handle(&self, event: Self::Event) {
-        for handler in &self.handlers {
-            handler.handle(event.clone())
+    fn handle(&self, event: Self::Event) -> Result<(), HandlerError> {
+        let errors: Vec<HandlerError> = self
+            .handlers
+            .iter()
+            .filter_map(|handler| handler.handle(event.clone()).err())
+            .collect();
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(HandlerError::Multiple(errors))
         }
     }
 
@@ -263,3 +707,188 @@ impl EventHandler for MultiHandler {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A handler that always succeeds, to pair against failing handlers in the
+    /// aggregation test below.
+    struct OkHandler;
+
+    impl EventHandler for OkHandler {
+        type Event = Message;
+
+        fn handle(&self, _event: Self::Event) -> Result<(), HandlerError> {
+            Ok(())
+        }
+
+        fn finish(&self) {}
+    }
+
+    /// A handler that always fails with the error its constructor returns, for
+    /// exercising `MultiHandler`'s error aggregation.
+    struct FailingHandler(fn() -> HandlerError);
+
+    impl EventHandler for FailingHandler {
+        type Event = Message;
+
+        fn handle(&self, _event: Self::Event) -> Result<(), HandlerError> {
+            Err((self.0)())
+        }
+
+        fn finish(&self) {}
+    }
+
+    #[test]
+    fn multi_handler_aggregates_errors_from_failing_handlers() {
+        let multi = MultiHandler::new()
+            .push(Box::new(FailingHandler(|| HandlerError::SendFailed)))
+            .push(Box::new(OkHandler))
+            .push(Box::new(FailingHandler(|| HandlerError::Closed)));
+
+        let err = multi
+            .handle(Message::CurrentStatus("ping".into()))
+            .expect_err("two of the three handlers should fail");
+
+        match err {
+            HandlerError::Multiple(errors) => {
+                assert_eq!(errors.len(), 2);
+                assert!(matches!(errors[0], HandlerError::SendFailed));
+                assert!(matches!(errors[1], HandlerError::Closed));
+            }
+            other => panic!("expected HandlerError::Multiple, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn disconnect_with_timeout_errors_if_writer_never_acknowledges() {
+        let (sender, _receiver) = crossbeam_channel::unbounded::<Message>();
+        let (_disconnect_sender, disconnect_receiver) = mpsc::channel::<Disconnect>();
+
+        let reporter = CargoMsrvReporter::setup(sender, disconnect_receiver);
+
+        let result = reporter.disconnect_with_timeout(Duration::from_millis(50));
+
+        assert!(matches!(result, Err(DisconnectError::Timeout)));
+    }
+
+    fn sample_messages() -> Vec<Message> {
+        vec![
+            Message::CurrentStatus("checking toolchains".into()),
+            Message::Event(Event::Installing),
+            Message::Event(Event::Updating("1.70.0".into())),
+            Message::Progression(Progression {
+                max: 10,
+                current: 3,
+            }),
+        ]
+    }
+
+    #[test]
+    fn json_format_roundtrips_every_message() {
+        let format = JsonFormat;
+        for message in sample_messages() {
+            let encoded = format.encode(&message).expect("encode should succeed");
+            let decoded: Message =
+                serde_json::from_slice(&encoded).expect("decode should succeed");
+            assert_eq!(decoded, message);
+        }
+    }
+
+    #[test]
+    fn message_pack_format_roundtrips_every_message() {
+        let format = MessagePackFormat;
+        for message in sample_messages() {
+            let encoded = format.encode(&message).expect("encode should succeed");
+            let decoded: Message =
+                rmp_serde::from_slice(&encoded).expect("decode should succeed");
+            assert_eq!(decoded, message);
+        }
+    }
+
+    #[test]
+    fn postcard_format_roundtrips_every_message() {
+        let format = PostcardFormat;
+        for message in sample_messages() {
+            let encoded = format.encode(&message).expect("encode should succeed");
+            let decoded: Message =
+                postcard::from_bytes(&encoded).expect("decode should succeed");
+            assert_eq!(decoded, message);
+        }
+    }
+
+    #[test]
+    fn bincode_format_roundtrips_every_message() {
+        let format = BincodeFormat;
+        for message in sample_messages() {
+            let encoded = format.encode(&message).expect("encode should succeed");
+            let decoded: Message =
+                bincode::deserialize(&encoded).expect("decode should succeed");
+            assert_eq!(decoded, message);
+        }
+    }
+
+    #[test]
+    fn cancel_stops_draining_further_queued_messages() {
+        let (sender, receiver) = crossbeam_channel::unbounded::<Message>();
+        let (disconnect_sender, disconnect_receiver) = mpsc::channel::<Disconnect>();
+
+        let writer =
+            CargoMsrvWriter::setup(receiver, sender.clone(), disconnect_sender, OkHandler, |_handler| {
+                Ok(())
+            });
+
+        // crossbeam_channel::select! picks ready operations at random, so queue a
+        // large batch before cancelling: if the writer ever drained these to
+        // completion instead of honouring the cancellation, it would do so
+        // overwhelmingly often across this many messages.
+        const QUEUED: usize = 10_000;
+        for i in 0..QUEUED {
+            sender
+                .send(Message::CurrentStatus(format!("event {}", i)))
+                .unwrap();
+        }
+        writer.cancel_sender().send(Cancel).unwrap();
+        drop(writer);
+        drop(sender);
+
+        let disconnect = disconnect_receiver.recv().unwrap();
+        assert!(
+            disconnect.events_processed < QUEUED,
+            "writer drained all {} queued messages instead of stopping on cancellation",
+            QUEUED
+        );
+    }
+
+    #[test]
+    fn disconnect_drains_every_queued_message_before_finishing() {
+        let (sender, receiver) = crossbeam_channel::unbounded::<Message>();
+        let (disconnect_sender, disconnect_receiver) = mpsc::channel::<Disconnect>();
+
+        let writer = CargoMsrvWriter::setup(
+            receiver,
+            sender.clone(),
+            disconnect_sender,
+            FailingHandler(|| HandlerError::SendFailed),
+            |_handler| Ok(()),
+        );
+        // Held alive past `drop(writer)` below: otherwise dropping `writer` drops its
+        // only `Cancel` sender too, disconnecting that channel and racing the writer's
+        // `select!` into exiting via cancellation before it drains the queue.
+        let _keep_cancel_channel_open = writer.cancel_sender();
+        drop(writer);
+
+        const QUEUED: usize = 25;
+        for i in 0..QUEUED {
+            sender
+                .send(Message::CurrentStatus(format!("event {}", i)))
+                .unwrap();
+        }
+        drop(sender);
+
+        let disconnect = disconnect_receiver.recv().unwrap();
+        assert_eq!(disconnect.events_processed, QUEUED);
+        assert!(matches!(disconnect.last_error, Some(HandlerError::SendFailed)));
+    }
+}